@@ -2,7 +2,7 @@ use std::ops::Range;
 
 use rand::Rng;
 
-use crate::BinaryPGA2;
+use crate::{BinaryPGA2, ProjectionMode};
 
 trait EvolvableNumeral {
     fn representation(&mut self) -> &mut BinaryPGA2;
@@ -13,7 +13,7 @@ trait EvolvableNumeral {
 
     fn mutate_resolution(&mut self, mutation_rate: f64, rng: &mut impl Rng) {
         if rng.gen_bool(mutation_rate) {
-            self.representation().increase_resolution()
+            self.representation().increase_resolution(rng)
         }
         if rng.gen_bool(mutation_rate) {
             self.representation().decrease_resolution()
@@ -25,6 +25,7 @@ trait EvolvableNumeral {
 pub struct EvolvableF64 {
     genome: BinaryPGA2,
     range: Range<f64>,
+    mode: ProjectionMode,
 }
 
 impl EvolvableNumeral for EvolvableF64 {
@@ -34,15 +35,101 @@ impl EvolvableNumeral for EvolvableF64 {
 }
 
 impl EvolvableF64 {
-    pub fn new(lower: f64, upper: f64) -> Self {
+    pub fn new(lower: f64, upper: f64, rng: &mut impl Rng) -> Self {
         Self {
-            genome: BinaryPGA2::new(),
+            genome: BinaryPGA2::new(rng),
             range: lower..upper,
+            mode: ProjectionMode::default(),
         }
     }
 
+    /// Create a new instance seeded deterministically, so an evolution run can be replayed bit-for-bit.
+    pub fn new_seeded(lower: f64, upper: f64, seed: u64) -> Self {
+        Self {
+            genome: BinaryPGA2::new_seeded(seed),
+            range: lower..upper,
+            mode: ProjectionMode::default(),
+        }
+    }
+
+    /// Resolution is capped here, since a caller-chosen `target_std` close to zero would
+    /// otherwise request a genome with more bits than can be allocated.
+    const MAX_RESOLUTION: usize = 1_024;
+
+    /// Create a new instance with the resolution sized so that the expressed value has
+    /// approximately `target_std` standard deviation.
+    ///
+    /// The expressed value is a rescaled `Binomial(n, 0.5)` count, so
+    /// `std(v) = (upper - lower) * 0.5 / sqrt(n)`. Inverting this for `n` and rounding up gives
+    /// the smallest resolution whose granularity is at least as fine as `target_std`, clamped to
+    /// between one bit and [`EvolvableF64::MAX_RESOLUTION`] bits. The actual granularity reached
+    /// can be read back via [`EvolvableF64::std`].
+    ///
+    /// Panics if `target_std` is not positive.
+    pub fn with_target_std(lower: f64, upper: f64, target_std: f64, rng: &mut impl Rng) -> Self {
+        assert!(target_std > 0.0, "target_std must be positive");
+
+        let n = (((upper - lower) * 0.5 / target_std).powi(2)).ceil() as usize;
+        let resolution = n.clamp(1, Self::MAX_RESOLUTION);
+
+        Self {
+            genome: BinaryPGA2::with_resolution(resolution, rng),
+            range: lower..upper,
+            mode: ProjectionMode::default(),
+        }
+    }
+
+    /// Sets the projection mode used to read the genome into a value, see [`ProjectionMode`].
+    pub fn set_mode(&mut self, mode: ProjectionMode) {
+        self.mode = mode;
+    }
+
     pub fn value(&self) -> f64 {
-        self.genome.f64(&self.range)
+        match self.mode {
+            ProjectionMode::Proportional => self.genome.f64(&self.range),
+            ProjectionMode::Positional => self.genome.f64_positional(&self.range),
+        }
+    }
+
+    /// Returns the theoretical standard deviation of the expressed value at the current resolution.
+    ///
+    /// This is the standard deviation under [`ProjectionMode::Proportional`]; the mode set via
+    /// [`EvolvableF64::set_mode`] is not taken into account.
+    pub fn std(&self) -> f64 {
+        (self.range.end - self.range.start) * 0.5 / (self.genome.resolution() as f64).sqrt()
+    }
+
+    /// Exports the genome and range into a compact byte form that can be persisted and later
+    /// restored with [`EvolvableF64::import`], so a checkpointed numeral resumes evolution
+    /// deterministically.
+    pub fn export(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend_from_slice(&self.range.start.to_le_bytes());
+        bytes.extend_from_slice(&self.range.end.to_le_bytes());
+        bytes.push(match self.mode {
+            ProjectionMode::Proportional => 0,
+            ProjectionMode::Positional => 1,
+        });
+        bytes.extend_from_slice(&self.genome.export());
+
+        bytes
+    }
+
+    /// Restores an EvolvableF64 from bytes produced by [`EvolvableF64::export`].
+    pub fn import(bytes: &[u8]) -> Self {
+        let lower = <f64>::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let upper = <f64>::from_le_bytes(bytes[8..16].try_into().unwrap());
+        let mode = match bytes[16] {
+            1 => ProjectionMode::Positional,
+            _ => ProjectionMode::Proportional,
+        };
+
+        Self {
+            genome: BinaryPGA2::import(&bytes[17..]),
+            range: lower..upper,
+            mode,
+        }
     }
 }
 
@@ -52,13 +139,69 @@ mod tests {
 
     #[test]
     fn generates_different_values() {
-        // let numeral = EvolvableF64::new(0.0, 1.0);
+        // let numeral = EvolvableF64::new(0.0, 1.0, &mut rand::thread_rng());
 
         // assert!((dbg!(numeral.value()) - 0.0).abs() < f64::EPSILON);
 
         for _ in 0..20 {
-            let numeral = EvolvableF64::new(0.0, 1.0);
+            let numeral = EvolvableF64::new(0.0, 1.0, &mut rand::thread_rng());
             dbg!(numeral.value());
         }
     }
+
+    #[test]
+    fn same_seed_reproduces_value() {
+        let a = EvolvableF64::new_seeded(0.0, 1.0, 7);
+        let b = EvolvableF64::new_seeded(0.0, 1.0, 7);
+
+        assert_eq!(a.value(), b.value());
+    }
+
+    #[test]
+    fn sizes_resolution_to_target_std() {
+        let numeral = EvolvableF64::with_target_std(0.0, 1.0, 0.05, &mut rand::thread_rng());
+
+        assert!(numeral.std() <= 0.05);
+    }
+
+    #[test]
+    fn resolution_is_at_least_one_bit() {
+        let numeral = EvolvableF64::with_target_std(0.0, 1.0, 10.0, &mut rand::thread_rng());
+
+        assert_eq!(numeral.std(), 0.5);
+    }
+
+    #[test]
+    fn resolution_is_capped_for_vanishingly_small_target_std() {
+        let numeral = EvolvableF64::with_target_std(0.0, 1.0, 1e-6, &mut rand::thread_rng());
+
+        assert_eq!(numeral.genome.resolution(), EvolvableF64::MAX_RESOLUTION);
+    }
+
+    #[test]
+    #[should_panic(expected = "target_std must be positive")]
+    fn with_target_std_rejects_zero() {
+        EvolvableF64::with_target_std(0.0, 1.0, 0.0, &mut rand::thread_rng());
+    }
+
+    #[test]
+    fn export_import_roundtrips() {
+        let numeral = EvolvableF64::new_seeded(-1.0, 1.0, 3);
+
+        let restored = EvolvableF64::import(&numeral.export());
+
+        assert_eq!(numeral.value(), restored.value());
+        assert_eq!(numeral.range, restored.range);
+    }
+
+    #[test]
+    fn export_import_preserves_positional_mode() {
+        let mut numeral = EvolvableF64::new_seeded(0.0, 1.0, 3);
+        numeral.set_mode(ProjectionMode::Positional);
+
+        let restored = EvolvableF64::import(&numeral.export());
+
+        assert_eq!(restored.mode, ProjectionMode::Positional);
+        assert_eq!(numeral.value(), restored.value());
+    }
 }
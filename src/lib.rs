@@ -1,12 +1,31 @@
 use std::ops::Range;
 
 use bitvec::vec::BitVec;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
+mod categorical;
 mod f64;
 
+pub use crate::categorical::CategoricalPGA;
 pub use crate::f64::EvolvableF64;
 
+/// Selects how a [`BinaryPGA2`] genome is projected into its expressed value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProjectionMode {
+    /// Interprets the genome as a proportion of set bits (see [`BinaryPGA2::f64`]).
+    ///
+    /// Clusters around the center of the range and yields small changes under mutation, at the
+    /// cost of capping resolution at `n + 1` distinct values.
+    #[default]
+    Proportional,
+    /// Interprets the genome as a binary fraction (see [`BinaryPGA2::f64_positional`]).
+    ///
+    /// Addresses every value the bit length can represent, at the cost of losing the CLT-style
+    /// small-mutation behavior.
+    Positional,
+}
+
 /// BinaryPGA2 is an implementation of the **P**roportional **G**enetic **A**lgorithm variant 2 introduced in ["The Proportional Genetic Algorithm: Gene Expression in a Genetic Algorithm"][0]  with an alphabet size of two.
 ///
 /// An interesting observation the paper to my knowledge did not make is the interpretation of the expressed value as a sum of random variables.
@@ -22,18 +41,24 @@ struct BinaryPGA2(BitVec);
 
 impl BinaryPGA2 {
     /// Create a new instance of BinaryPGA2 with one bit initial resolution.
-    pub fn new() -> Self {
+    pub fn new(rng: &mut impl Rng) -> Self {
         let mut data = BitVec::EMPTY;
 
-        data.push(rand::thread_rng().gen());
+        data.push(rng.gen());
 
         Self(data)
     }
 
-    /// Create a new instance of BinaryPGA2 with one bit initial resolution.
-    pub fn with_resolution(resolution: usize) -> Self {
+    /// Create a new instance of BinaryPGA2 with one bit initial resolution, seeded deterministically.
+    ///
+    /// Useful for reproducing an evolution run bit-for-bit from a single seed.
+    pub fn new_seeded(seed: u64) -> Self {
+        Self::new(&mut StdRng::seed_from_u64(seed))
+    }
+
+    /// Create a new instance of BinaryPGA2 with the given initial resolution.
+    pub fn with_resolution(resolution: usize, rng: &mut impl Rng) -> Self {
         let mut data = BitVec::with_capacity(resolution);
-        let mut rng = rand::thread_rng();
 
         for _ in 0..resolution {
             data.push(rng.gen());
@@ -42,9 +67,14 @@ impl BinaryPGA2 {
         Self(data)
     }
 
+    /// Create a new instance of BinaryPGA2 with the given initial resolution, seeded deterministically.
+    pub fn with_resolution_seeded(resolution: usize, seed: u64) -> Self {
+        Self::with_resolution(resolution, &mut StdRng::seed_from_u64(seed))
+    }
+
     /// Adds a random bit to the underlying BitVec thereby increasing the resolution.
-    fn increase_resolution(&mut self) {
-        self.0.push(rand::thread_rng().gen())
+    fn increase_resolution(&mut self, rng: &mut impl Rng) {
+        self.0.push(rng.gen())
     }
 
     /// Removes a bit from the underlying BitVec by popping a bit.
@@ -54,6 +84,11 @@ impl BinaryPGA2 {
         }
     }
 
+    /// Returns the number of bits in the underlying BitVec, i.e. the resolution.
+    pub(crate) fn resolution(&self) -> usize {
+        self.0.len()
+    }
+
     /// Flips every bit in the underlying BitVec with given `mutation_rate`.
     ///
     /// `mutation_rate` needs to be in the range `0.0..=1.0`.
@@ -79,6 +114,74 @@ impl BinaryPGA2 {
     pub fn f32(&self, range: &Range<f32>) -> f32 {
         (self.0.count_ones() as f32 / self.0.len() as f32) * (range.end - range.start) + range.start
     }
+
+    /// Returns the PGA interpretet as an f64 in the given range by reading the BitVec MSB first
+    /// as a binary fraction in `[0, 1)`, i.e. `sum(bit_i * 2^-(i+1))`.
+    ///
+    /// Unlike [`BinaryPGA2::f64`] this addresses every value the bit length can represent instead
+    /// of clustering around the center of the range, at the cost of the CLT-style small-mutation
+    /// behavior.
+    pub fn f64_positional(&self, range: &Range<f64>) -> f64 {
+        let fraction: f64 = self
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, bit)| **bit)
+            .map(|(i, _)| 2f64.powi(-(i as i32 + 1)))
+            .sum();
+
+        fraction * (range.end - range.start) + range.start
+    }
+
+    /// Returns the PGA interpretet as an f32 in the given range by reading the BitVec MSB first
+    /// as a binary fraction in `[0, 1)`, i.e. `sum(bit_i * 2^-(i+1))`.
+    ///
+    /// See [`BinaryPGA2::f64_positional`] for the rationale.
+    pub fn f32_positional(&self, range: &Range<f32>) -> f32 {
+        let fraction: f32 = self
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, bit)| **bit)
+            .map(|(i, _)| 2f32.powi(-(i as i32 + 1)))
+            .sum();
+
+        fraction * (range.end - range.start) + range.start
+    }
+
+    /// Packs the underlying BitVec into bytes, MSB first, prefixed with a 4 byte little-endian
+    /// bit length so the exact resolution survives the round trip.
+    pub(crate) fn export(&self) -> Vec<u8> {
+        let len = self.0.len() as u32;
+        let mut bytes = Vec::with_capacity(4 + self.0.len().div_ceil(8));
+
+        bytes.extend_from_slice(&len.to_le_bytes());
+
+        for chunk in self.0.chunks(8) {
+            let mut byte = 0u8;
+            for (i, bit) in chunk.iter().enumerate() {
+                if *bit {
+                    byte |= 1 << (7 - i);
+                }
+            }
+            bytes.push(byte);
+        }
+
+        bytes
+    }
+
+    /// Reconstructs a BinaryPGA2 from bytes produced by [`BinaryPGA2::export`].
+    pub(crate) fn import(bytes: &[u8]) -> Self {
+        let len = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+        let mut data = BitVec::with_capacity(len);
+
+        for bit_index in 0..len {
+            let byte = bytes[4 + bit_index / 8];
+            data.push(byte & (1 << (7 - bit_index % 8)) != 0);
+        }
+
+        Self(data)
+    }
 }
 
 #[cfg(test)]
@@ -87,30 +190,30 @@ mod tests {
 
     #[test]
     fn starts_with_one_bit_resolution() {
-        let pga2 = BinaryPGA2::new();
+        let pga2 = BinaryPGA2::new(&mut rand::thread_rng());
 
         assert_eq!(pga2.0.len(), 1);
     }
 
     #[test]
     fn specify_initial_resolution() {
-        let pga2 = BinaryPGA2::with_resolution(10);
+        let pga2 = BinaryPGA2::with_resolution(10, &mut rand::thread_rng());
 
         assert_eq!(pga2.0.len(), 10);
     }
 
     #[test]
     fn increase_resolution() {
-        let mut pga2 = BinaryPGA2::with_resolution(1);
+        let mut pga2 = BinaryPGA2::with_resolution(1, &mut rand::thread_rng());
 
-        pga2.increase_resolution();
+        pga2.increase_resolution(&mut rand::thread_rng());
 
         assert_eq!(pga2.0.len(), 2);
     }
 
     #[test]
     fn decrease_resolution() {
-        let mut pga2 = BinaryPGA2::with_resolution(2);
+        let mut pga2 = BinaryPGA2::with_resolution(2, &mut rand::thread_rng());
 
         pga2.decrease_resolution();
 
@@ -119,16 +222,52 @@ mod tests {
 
     #[test]
     fn resolution_is_at_least_one() {
-        let mut pga2 = BinaryPGA2::with_resolution(1);
+        let mut pga2 = BinaryPGA2::with_resolution(1, &mut rand::thread_rng());
 
         pga2.decrease_resolution();
 
         assert_eq!(pga2.0.len(), 1);
     }
 
+    #[test]
+    fn same_seed_reproduces_genome() {
+        let a = BinaryPGA2::with_resolution_seeded(16, 42);
+        let b = BinaryPGA2::with_resolution_seeded(16, 42);
+
+        assert_eq!(a.0, b.0);
+    }
+
+    #[test]
+    fn new_seeded_reproduces_genome() {
+        let a = BinaryPGA2::new_seeded(42);
+        let b = BinaryPGA2::new_seeded(42);
+
+        assert_eq!(a.0, b.0);
+    }
+
+    #[test]
+    fn positional_projection_addresses_full_precision() {
+        let mut data = BitVec::EMPTY;
+        data.push(true);
+        data.push(false);
+        let pga2 = BinaryPGA2(data);
+
+        // 0.5 + 0.0 = 0.5
+        assert_eq!(pga2.f64_positional(&(0.0..1.0)), 0.5);
+    }
+
+    #[test]
+    fn export_import_roundtrips() {
+        let pga2 = BinaryPGA2::with_resolution(13, &mut rand::thread_rng());
+
+        let restored = BinaryPGA2::import(&pga2.export());
+
+        assert_eq!(pga2.0, restored.0);
+    }
+
     #[test]
     fn flips_all_bits() {
-        let mut pga2 = BinaryPGA2::with_resolution(10);
+        let mut pga2 = BinaryPGA2::with_resolution(10, &mut rand::thread_rng());
 
         let initial_state = pga2.0.clone();
 